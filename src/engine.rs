@@ -0,0 +1,328 @@
+// Core level/player model factored out of `main` so the collision and
+// movement math can be exercised without a terminal attached. `main` is now
+// a thin wrapper: it loads a `Level`, drives a `Player` from input events
+// via `apply_movement`, and leaves the raycasting/rendering as-is.
+
+use std::fs;
+use std::io;
+
+/// Turn rate, in radians/sec, applied by `Movement::TurnLeft`/`TurnRight`.
+pub const PLAYER_ROT_V: f64 = 5.0;
+/// Move speed, in world units/sec, applied by `Movement::Forward`/`Backward`.
+pub const PLAYER_V: f64 = 10.0;
+
+/// A single map cell. Walls carry the glyph they were drawn with so the
+/// renderer can still look up which texture/material to sample.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Tile {
+    Empty,
+    Wall(char),
+}
+
+impl Tile {
+    fn from_char(c: char) -> Tile {
+        if c == '.' || c == ' ' {
+            Tile::Empty
+        } else {
+            Tile::Wall(c)
+        }
+    }
+
+    /// The map glyph this tile was parsed from (`.` for open floor).
+    pub fn glyph(self) -> char {
+        match self {
+            Tile::Empty => '.',
+            Tile::Wall(c) => c,
+        }
+    }
+
+    pub fn is_wall(self) -> bool {
+        matches!(self, Tile::Wall(_))
+    }
+}
+
+/// A loaded map: a rectangular grid of tiles addressed `(row, col)`, the
+/// same indexing convention the original inline raycaster used (a
+/// player's x-coordinate is the row, y-coordinate is the column).
+pub struct Level {
+    pub width: usize,
+    pub height: usize,
+    tiles: Vec<Tile>,
+}
+
+/// Parses a leading `WIDTHxHEIGHT` header line (e.g. `16x16`), if present.
+fn parse_header(line: &str) -> Option<(usize, usize)> {
+    let (w, h) = line.split_once('x')?;
+    Some((w.trim().parse().ok()?, h.trim().parse().ok()?))
+}
+
+impl Level {
+    /// Parses the existing `#`/`.` map format. An optional `WIDTHxHEIGHT`
+    /// header line may precede the grid; without one the dimensions are
+    /// inferred from the longest line and the number of lines.
+    pub fn from_str(s: &str) -> Level {
+        let mut lines: Vec<&str> = s.lines().filter(|l| !l.is_empty()).collect();
+
+        let header = lines.first().and_then(|l| parse_header(l));
+        if header.is_some() {
+            lines.remove(0);
+        }
+
+        let height = header.map(|(_, h)| h).unwrap_or(lines.len());
+        let width = header
+            .map(|(w, _)| w)
+            .unwrap_or_else(|| lines.iter().map(|l| l.chars().count()).max().unwrap_or(0));
+
+        let mut tiles = vec![Tile::Empty; width * height];
+        for (row, line) in lines.iter().take(height).enumerate() {
+            for (col, c) in line.chars().take(width).enumerate() {
+                tiles[row * width + col] = Tile::from_char(c);
+            }
+        }
+
+        Level {
+            width,
+            height,
+            tiles,
+        }
+    }
+
+    /// Loads and parses a map from disk, so maps don't have to be compiled
+    /// into the binary.
+    pub fn from_file(path: &str) -> io::Result<Level> {
+        let contents = fs::read_to_string(path)?;
+        Ok(Level::from_str(&contents))
+    }
+
+    /// Whether `pos` (row, col) falls within the level's bounds. Doesn't
+    /// say anything about whether that cell is open floor - see `tile_at`.
+    pub fn contains(&self, pos: (f64, f64)) -> bool {
+        pos.0 >= 0.0
+            && pos.1 >= 0.0
+            && (pos.0 as usize) < self.height
+            && (pos.1 as usize) < self.width
+    }
+
+    /// The tile at `pos` (row, col). Out-of-bounds positions read as a
+    /// solid wall, so callers don't need a separate bounds check before
+    /// treating the result as collidable.
+    pub fn tile_at(&self, pos: (f64, f64)) -> Tile {
+        if !self.contains(pos) {
+            return Tile::Wall('#');
+        }
+        let row = pos.0 as usize;
+        let col = pos.1 as usize;
+        self.tiles[row * self.width + col]
+    }
+
+    /// The raw glyph at a given (row, col) grid cell, for minimap display.
+    pub fn glyph_at(&self, row: usize, col: usize) -> char {
+        self.tiles[row * self.width + col].glyph()
+    }
+}
+
+/// A movement command for one `apply_movement` call.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Movement {
+    Forward,
+    Backward,
+    TurnLeft,
+    TurnRight,
+}
+
+/// Eye height while standing, in normalized wall-height units (a wall
+/// spans 0.0 at the floor to 1.0 at the ceiling).
+pub const EYE_STANDING: f64 = 0.5;
+/// Eye height while crouching.
+pub const EYE_CROUCHING: f64 = 0.25;
+/// How much headroom to leave below the ceiling at the top of a jump, so
+/// the camera can't clip through it.
+pub const HEAD_MARGIN: f64 = 0.1;
+/// Look rate, in radians/sec; `main` scales it by frame time to get the
+/// delta it passes to `Player::look`.
+pub const PITCH_V: f64 = 1.0;
+/// Pitch is clamped to +/- this so the horizon can't leave the screen.
+pub const MAX_PITCH: f64 = 0.3;
+/// Initial upward velocity imparted by `Player::jump`.
+pub const JUMP_VELOCITY: f64 = 1.2;
+pub const GRAVITY: f64 = 3.0;
+
+/// The player's position (row, col), facing angle, and vertical look/stance
+/// state in a `Level`.
+pub struct Player {
+    pub pos: (f64, f64),
+    pub angle: f64,
+    /// Look up/down offset, in radians.
+    pub pitch: f64,
+    /// Eye height, in normalized wall-height units.
+    pub z: f64,
+    /// Vertical velocity, for the jump/gravity integration in
+    /// `integrate_vertical`.
+    vz: f64,
+    pub crouching: bool,
+}
+
+impl Player {
+    pub fn new(pos: (f64, f64), angle: f64) -> Player {
+        Player {
+            pos,
+            angle,
+            pitch: 0.0,
+            z: EYE_STANDING,
+            vz: 0.0,
+            crouching: false,
+        }
+    }
+
+    /// Applies one movement command for `dt` seconds, colliding against
+    /// `level`: the move is taken only if it doesn't land in a wall.
+    pub fn apply_movement(&mut self, movement: Movement, level: &Level, dt: f64) {
+        match movement {
+            Movement::TurnLeft => self.angle -= PLAYER_ROT_V * dt,
+            Movement::TurnRight => self.angle += PLAYER_ROT_V * dt,
+            Movement::Forward => self.step(level, dt, 1.0),
+            Movement::Backward => self.step(level, dt, -1.0),
+        }
+    }
+
+    fn step(&mut self, level: &Level, dt: f64, sign: f64) {
+        let new_pos = (
+            self.pos.0 + self.angle.sin() * PLAYER_V * dt * sign,
+            self.pos.1 + self.angle.cos() * PLAYER_V * dt * sign,
+        );
+        if !level.tile_at(new_pos).is_wall() {
+            self.pos = new_pos;
+        }
+    }
+
+    /// Adjusts pitch by `delta` radians, clamped to `MAX_PITCH`.
+    pub fn look(&mut self, delta: f64) {
+        self.pitch = (self.pitch + delta).clamp(-MAX_PITCH, MAX_PITCH);
+    }
+
+    pub fn set_crouching(&mut self, crouching: bool) {
+        self.crouching = crouching;
+    }
+
+    fn airborne(&self) -> bool {
+        self.vz != 0.0 || self.z > EYE_STANDING + 1e-9
+    }
+
+    /// Starts a jump, unless already airborne or crouching (can't jump from
+    /// a duck, matching how most shooters gate it).
+    pub fn jump(&mut self) {
+        if !self.airborne() && !self.crouching {
+            self.vz = JUMP_VELOCITY;
+        }
+    }
+
+    /// Integrates gravity against the current vertical velocity for `dt`
+    /// seconds, clamping so standing/crouching settle at their resting
+    /// height and a jump's apex can't carry the eye through the ceiling.
+    pub fn integrate_vertical(&mut self, dt: f64) {
+        let resting_height = if self.crouching {
+            EYE_CROUCHING
+        } else {
+            EYE_STANDING
+        };
+
+        if self.airborne() {
+            self.vz -= GRAVITY * dt;
+            self.z += self.vz * dt;
+
+            let max_z = 1.0 - HEAD_MARGIN;
+            if self.z > max_z {
+                self.z = max_z;
+                self.vz = self.vz.min(0.0);
+            }
+            if self.z <= resting_height && self.vz <= 0.0 {
+                self.z = resting_height;
+                self.vz = 0.0;
+            }
+        } else {
+            self.z = resting_height;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_level() -> Level {
+        Level::from_str("####\n#..#\n#..#\n####")
+    }
+
+    #[test]
+    fn movement_is_blocked_by_a_wall() {
+        let level = test_level();
+        let mut player = Player::new((1.5, 1.0), 0.0);
+        for _ in 0..1000 {
+            player.apply_movement(Movement::Forward, &level, 0.01);
+        }
+        // Started at col 1.0 heading toward the wall at col 3; collision
+        // should have stopped it short, not let it tunnel through.
+        assert!(player.pos.1 > 1.5);
+        assert!(player.pos.1 < 3.0);
+    }
+
+    #[test]
+    fn jump_rises_then_settles_back_to_standing() {
+        let mut player = Player::new((1.5, 1.5), 0.0);
+        player.jump();
+
+        let mut saw_apex = false;
+        for _ in 0..1000 {
+            if player.z > EYE_STANDING {
+                saw_apex = true;
+            }
+            player.integrate_vertical(0.01);
+        }
+        assert!(saw_apex);
+        assert_eq!(player.z, EYE_STANDING);
+    }
+
+    #[test]
+    fn jump_cannot_clip_through_the_ceiling() {
+        let mut player = Player::new((1.5, 1.5), 0.0);
+        player.vz = 100.0; // an unrealistically large launch velocity
+        for _ in 0..100 {
+            player.integrate_vertical(0.01);
+            assert!(player.z <= 1.0 - HEAD_MARGIN + 1e-9);
+        }
+    }
+
+    #[test]
+    fn crouching_lowers_eye_height() {
+        let mut player = Player::new((1.5, 1.5), 0.0);
+        player.integrate_vertical(0.01);
+        assert_eq!(player.z, EYE_STANDING);
+
+        player.set_crouching(true);
+        player.integrate_vertical(0.01);
+        assert_eq!(player.z, EYE_CROUCHING);
+    }
+
+    #[test]
+    fn contains_rejects_out_of_bounds_positions() {
+        let level = test_level();
+        assert!(level.contains((1.0, 1.0)));
+        assert!(!level.contains((-1.0, 1.0)));
+        assert!(!level.contains((1.0, -1.0)));
+        assert!(!level.contains((100.0, 1.0)));
+        assert!(!level.contains((1.0, 100.0)));
+    }
+
+    #[test]
+    fn full_rotation_returns_to_the_same_facing() {
+        let level = test_level();
+        let mut player = Player::new((1.5, 1.5), 0.7);
+        let (start_sin, start_cos) = (player.angle.sin(), player.angle.cos());
+
+        let full_turn_dt = (2.0 * std::f64::consts::PI) / PLAYER_ROT_V;
+        player.apply_movement(Movement::TurnRight, &level, full_turn_dt);
+
+        assert!((player.angle.sin() - start_sin).abs() < 1e-9);
+        assert!((player.angle.cos() - start_cos).abs() < 1e-9);
+    }
+}
@@ -7,13 +7,23 @@ use termion::input::TermRead;
 use termion::raw::IntoRawMode;
 use termion::terminal_size;
 
+mod engine;
+mod net;
+mod sector;
+
 // Rendering constants
-const FOV: f64 = 3.14159 / 4.0;
+pub(crate) const FOV: f64 = 3.14159 / 4.0;
 const MAX_RENDER_DIST: f64 = 16.0;
 
-struct Screen {
-    width: usize,
-    height: usize,
+// The sector world's vertical units aren't normalized to 0..1 like
+// `engine::EYE_STANDING` is, so the demo world's hand-picked standing eye
+// height is tracked separately; `player.z`'s jump/crouch delta from
+// `engine::EYE_STANDING` is added on top of it each frame.
+const SECTOR_EYE_BASELINE: f64 = 0.5;
+
+pub(crate) struct Screen {
+    pub(crate) width: usize,
+    pub(crate) height: usize,
     screen: Vec<char>,
 }
 
@@ -27,7 +37,7 @@ impl Screen {
     }
 
     // Writes s into screen vector.
-    fn draw(&mut self, x: usize, y: usize, s: &str) {
+    pub(crate) fn draw(&mut self, x: usize, y: usize, s: &str) {
         let offset = y * self.width + x;
         for (i, c) in s.chars().enumerate() {
             if offset + i >= self.screen.len() {
@@ -44,7 +54,7 @@ impl Screen {
     }
 }
 
-fn wall_shade(dist: f64) -> char {
+pub(crate) fn wall_shade(dist: f64) -> char {
     if dist <= MAX_RENDER_DIST / 4.0 {
         return std::char::from_u32(0x2588).unwrap();
     } else if dist < MAX_RENDER_DIST / 3.0 {
@@ -57,6 +67,71 @@ fn wall_shade(dist: f64) -> char {
     return ' ';
 }
 
+const TEX_SIZE: usize = 8;
+
+// Small ASCII art tiles, one per wall glyph the map format supports. Keyed
+// by the map character so authors pick a material just by which glyph they
+// draw a wall with.
+fn wall_texture(glyph: char) -> [&'static str; TEX_SIZE] {
+    match glyph {
+        '=' => [
+            "========", "=      =", "=      =", "========", "=      =", "=      =", "========",
+            "=      =",
+        ],
+        '*' => [
+            "*.*.*.*.", ".*.*.*.*", "*.*.*.*.", ".*.*.*.*", "*.*.*.*.", ".*.*.*.*", "*.*.*.*.",
+            ".*.*.*.*",
+        ],
+        _ => [
+            "########", "#  #  #.", "#  #  #.", "########", "#  #  #.", "#  #  #.", "########",
+            "#  #  #.",
+        ],
+    }
+}
+
+// Samples a wall texture at fractional column `u` (0..1 across the wall
+// face) and screen-space row fraction `v` (0..1 from ceiling to floor).
+fn sample_texture(glyph: char, u: f64, v: f64) -> char {
+    let rows = wall_texture(glyph);
+    let tex_x = ((u * TEX_SIZE as f64) as usize).min(TEX_SIZE - 1);
+    let tex_y = ((v * TEX_SIZE as f64) as usize).min(TEX_SIZE - 1);
+    rows[tex_y].chars().nth(tex_x).unwrap_or('#')
+}
+
+// Rough "ink density" of a glyph, low to high, so a sampled texture pixel
+// and a `wall_shade` ramp character can be compared on the same scale.
+fn glyph_density(c: char) -> u8 {
+    match c {
+        ' ' => 0,
+        '.' => 1,
+        '*' => 2,
+        '=' => 3,
+        '#' => 4,
+        '\u{2591}' => 1, // light shade block
+        '\u{2592}' => 2, // medium shade block
+        '\u{2593}' => 3, // dark shade block
+        '\u{2588}' => 4, // full block
+        _ => 2,
+    }
+}
+
+// Shades a sampled texture glyph by distance, using the existing block-ramp
+// as a brightness multiplier: `wall_shade` is densest up close and fades out
+// with distance, so a texture pixel keeps showing its own detail for as long
+// as the ramp is at least that dense, and only gets replaced by the flatter
+// ramp character once distance has faded the ramp below it - the sparse parts
+// of a texture (its gaps and dots) keep their shape longer, while its bold
+// lines are the first to flatten into the ramp character, the same way a
+// bare wall_shade column fades.
+fn shade_texture_glyph(glyph: char, dist: f64) -> char {
+    let ramp = wall_shade(dist);
+    if glyph_density(ramp) >= glyph_density(glyph) {
+        glyph
+    } else {
+        ramp
+    }
+}
+
 fn main() {
     let map_str = "################
 #.....#........#
@@ -65,28 +140,52 @@ fn main() {
 #.....#........#
 #.....#........#
 #.....#........#
-#....##...######
+#....##...==*==#
 #....#.........#
 #..............#
-##########.....#
+#####*====.....#
 #..............#
 #..............#
 #..............#
 #..............#
 ################";
 
-    // Parse map into 2d vector
-    let mut map: Vec<Vec<char>> = Vec::new();
-    map.push(Vec::new());
-    let mut line = 0;
-    for c in map_str.chars() {
-        if c != '\n' {
-            map[line].push(c);
-        } else {
-            map.push(Vec::new());
-            line += 1;
-        }
-    }
+    // Maps are loadable at runtime via `--map <path>`; without one, fall
+    // back to the map compiled into the binary above.
+    let args: Vec<String> = std::env::args().collect();
+    let map_path = args
+        .iter()
+        .position(|a| a == "--map")
+        .and_then(|i| args.get(i + 1));
+    let level = match map_path {
+        Some(path) => engine::Level::from_file(path).expect("failed to load --map file"),
+        None => engine::Level::from_str(map_str),
+    };
+
+    // Sector/portal rendering is an alternate mode to the grid raycaster
+    // in the render loop below, enabled with `--sectors` on the command line. It trades the
+    // flat per-cell map for a small hand-built demo world so rooms can have
+    // distinct floor/ceiling heights.
+    let sector_mode = std::env::args().any(|a| a == "--sectors");
+    let world = sector::demo_world();
+
+    // Networked 2-player mode, enabled by passing `--port <local> --peer
+    // <addr>` on the command line. The simulation itself runs in `net`, at a
+    // fixed 60 Hz tick decoupled from this render loop's variable frame
+    // rate, so both peers stay in lockstep.
+    let net_args: Vec<String> = std::env::args().collect();
+    let net_config = net::NetConfig::from_args(&net_args);
+    let net_mode = net_config.is_some();
+    let net_socket = net_config
+        .as_ref()
+        .map(|c| net::bind(c).expect("bind UDP socket"));
+    let mut net_state = net::GameState::default();
+    let mut net_rollback = net::Rollback::new(net_state);
+    let mut net_local_hist = [net::PlayerInput::new(); net::ROLLBACK_WINDOW];
+    let mut net_remote_hist = [net::PlayerInput::new(); net::ROLLBACK_WINDOW];
+    let mut net_last_remote_input = net::PlayerInput::new();
+    let mut net_tick_accum = 0.0f64;
+    let mut net_local_input = net::PlayerInput::new();
 
     // Spawn thread to listen for user input events
     let (tx, rx) = mpsc::channel();
@@ -103,9 +202,15 @@ fn main() {
 
     let mut t_prev = Instant::now();
 
-    let mut player_x = 1.0;
-    let mut player_y = 1.0;
-    let mut player_angle = 0.0;
+    let mut player = engine::Player::new((1.0, 1.0), 0.0);
+
+    let mut sector_player = sector::Player {
+        pos: (2.5, 2.5),
+        angle: 0.0,
+        z: SECTOR_EYE_BASELINE,
+        pitch: 0.0,
+        sector: 0,
+    };
 
     // Clear terminal
     write!(stdout, "{}", termion::clear::All).unwrap();
@@ -123,8 +228,6 @@ fn main() {
         //
         // Handle input
         //
-        const PLAYER_V: f64 = 10.0;
-        const PLAYER_ROT_V: f64 = 5.0;
 
         //  Check for input on user input channel and update character position
         for event in rx.try_recv() {
@@ -134,105 +237,325 @@ fn main() {
                     write!(stdout, "{}", termion::clear::All).unwrap();
                     return;
                 }
-                Event::Key(Key::Left) => {
-                    player_angle -= PLAYER_ROT_V * t_elapsed.as_secs_f64() as f64
-                }
-                Event::Key(Key::Right) => {
-                    player_angle += PLAYER_ROT_V * t_elapsed.as_secs_f64() as f64
-                }
-                Event::Key(Key::Up) => {
-                    player_x += player_angle.sin() * PLAYER_V * t_elapsed.as_secs_f64();
-                    player_y += player_angle.cos() * PLAYER_V * t_elapsed.as_secs_f64();
-                    // Collision detection
-                    if map[player_x as usize][player_y as usize] == '#' {
-                        player_x -= player_angle.sin() * PLAYER_V * t_elapsed.as_secs_f64();
-                        player_y -= player_angle.cos() * PLAYER_V * t_elapsed.as_secs_f64();
-                    }
-                }
-                Event::Key(Key::Down) => {
-                    player_x -= player_angle.sin() * PLAYER_V * t_elapsed.as_secs_f64();
-                    player_y -= player_angle.cos() * PLAYER_V * t_elapsed.as_secs_f64();
-                    // Collision detection
-                    if map[player_x as usize][player_y as usize] == '#' {
-                        player_x += player_angle.sin() * PLAYER_V * t_elapsed.as_secs_f64();
-                        player_y += player_angle.cos() * PLAYER_V * t_elapsed.as_secs_f64();
-                    }
+                Event::Key(Key::Left) if net_mode => net_local_input.set_turn_left(true),
+                Event::Key(Key::Right) if net_mode => net_local_input.set_turn_right(true),
+                Event::Key(Key::Up) if net_mode => net_local_input.set_forward(true),
+                Event::Key(Key::Down) if net_mode => net_local_input.set_back(true),
+                Event::Key(Key::Char(' ')) if net_mode => net_local_input.set_fire(true),
+                Event::Key(Key::Left) => player.apply_movement(
+                    engine::Movement::TurnLeft,
+                    &level,
+                    t_elapsed.as_secs_f64(),
+                ),
+                Event::Key(Key::Right) => player.apply_movement(
+                    engine::Movement::TurnRight,
+                    &level,
+                    t_elapsed.as_secs_f64(),
+                ),
+                Event::Key(Key::Up) => player.apply_movement(
+                    engine::Movement::Forward,
+                    &level,
+                    t_elapsed.as_secs_f64(),
+                ),
+                Event::Key(Key::Down) => player.apply_movement(
+                    engine::Movement::Backward,
+                    &level,
+                    t_elapsed.as_secs_f64(),
+                ),
+                Event::Key(Key::PageUp) => player.look(engine::PITCH_V * t_elapsed.as_secs_f64()),
+                Event::Key(Key::PageDown) => {
+                    player.look(-engine::PITCH_V * t_elapsed.as_secs_f64())
                 }
+                Event::Key(Key::Char('c')) => player.set_crouching(!player.crouching),
+                Event::Key(Key::Char(' ')) => player.jump(),
                 _ => {
                     screen.draw(0, 75, &format!("got unexpected event: {:?}", event));
                 }
             }
         }
 
+        // Gravity/jump integration runs every frame regardless of input, so
+        // a jump keeps rising and falling even if no key is pressed mid-air.
+        player.integrate_vertical(t_elapsed.as_secs_f64());
+
+        // Advance the networked simulation at a fixed 60 Hz tick, however
+        // many (or few) ticks the elapsed render time covers. Each tick we
+        // predict the remote player's input as "whatever it did last tick"
+        // and send our own input for that frame number to every peer.
+        if net_mode {
+            let socket = net_socket.as_ref().unwrap();
+            let config = net_config.as_ref().unwrap();
+
+            net_tick_accum += t_elapsed.as_secs_f64();
+            while net_tick_accum >= net::TICK_DT.to_f64() {
+                net_tick_accum -= net::TICK_DT.to_f64();
+
+                let frame = net_state.frame;
+                let slot = frame as usize % net::ROLLBACK_WINDOW;
+                net_local_hist[slot] = net_local_input;
+                net_remote_hist[slot] = net_last_remote_input;
+                net_rollback.record(frame, net_state, net_last_remote_input);
+
+                net::advance(&mut net_state, [net_local_input, net_last_remote_input]);
+                net::send_input(
+                    socket,
+                    config,
+                    net::FrameInput {
+                        frame,
+                        input: net_local_input,
+                    },
+                );
+
+                // Input is sampled as a per-tick impulse, matching how the
+                // rest of this event loop treats discrete key events.
+                net_local_input = net::PlayerInput::new();
+            }
+
+            // Reconcile any remote input that just arrived against what we
+            // predicted for that frame, rolling back and re-simulating
+            // forward if they disagree.
+            for frame_input in net::poll_inputs(socket) {
+                let slot = frame_input.frame as usize % net::ROLLBACK_WINDOW;
+                if frame_input.frame < net_state.frame {
+                    net_state = net_rollback.reconcile(
+                        frame_input.frame,
+                        frame_input.input,
+                        net_state,
+                        |f| net_local_hist[f as usize % net::ROLLBACK_WINDOW],
+                        |f| {
+                            if f == frame_input.frame {
+                                frame_input.input
+                            } else {
+                                net_remote_hist[f as usize % net::ROLLBACK_WINDOW]
+                            }
+                        },
+                    );
+                }
+                net_remote_hist[slot] = frame_input.input;
+                net_last_remote_input = frame_input.input;
+            }
+
+            player.pos = (
+                net_state.players[0].x.to_f64(),
+                net_state.players[0].y.to_f64(),
+            );
+            player.angle = net_state.players[0].angle.to_f64();
+        }
+
+        // The rest of this loop still works in terms of plain player_x/y/angle
+        // locals, now derived from the authoritative `Player` each frame.
+        let player_x = player.pos.0;
+        let player_y = player.pos.1;
+        let player_angle = player.angle;
+
+        // Sector mode shares the same input-driven player_x/y/angle locals
+        // as the grid raycaster; re-locate the sector each frame in case
+        // movement carried the player through a portal. `demo_world()`'s
+        // polygons only cover a small footprint of the grid map, so outside
+        // it `locate` comes back empty - track that rather than rendering
+        // from a stale `sector_player.sector` that no longer contains the
+        // player's actual position.
+        sector_player.pos = (player_x, player_y);
+        sector_player.angle = player_angle;
+        // Jump/crouch and look offsets are driven by the same `player`, just
+        // carried over to the sector world's own vertical scale.
+        sector_player.z = SECTOR_EYE_BASELINE + (player.z - engine::EYE_STANDING);
+        sector_player.pitch = player.pitch;
+        let sector_located = world.locate(sector_player.pos, sector_player.sector);
+        if let Some(found) = sector_located {
+            sector_player.sector = found;
+        }
+
         //
         // Handle drawing
         //
 
-        // Ray casting
-        for x in 0..screen.width {
-            // For each column, calculate the projected ray angle into world space
-            let ray_angle: f64 =
-                (player_angle - FOV / 2.0) + (x as f64 / screen.width as f64) * FOV;
-
-            // Find distance to wall
-            let step_size = 0.1;
-            let mut ray_distance = 0.0;
-            let mut hit_wall = false;
-
-            // Unit vector for ray in player space
-            let eye_x = ray_angle.sin();
-            let eye_y = ray_angle.cos();
-
-            // Incrementally cast ray from player, along ray angle, testing for
-            // intersection with a block
-            while !hit_wall && ray_distance < MAX_RENDER_DIST {
-                ray_distance += step_size;
-                let dx = player_x + eye_x * ray_distance;
-                let dy = player_y + eye_y * ray_distance;
-
-                // Test if ray is out of bounds
-                if dx < 0.0 || dy >= screen.width as f64 || dy < 0.0 || dy >= screen.height as f64 {
-                    // Just set distance to maximum depth
-                    hit_wall = true;
-                    ray_distance = MAX_RENDER_DIST;
+        if sector_mode {
+            match sector_located {
+                Some(_) => sector::render(&mut screen, &world, &sector_player, FOV),
+                None => screen.draw(0, 0, "outside sector world bounds"),
+            }
+        } else {
+            // Ray casting
+            for x in 0..screen.width {
+                // For each column, calculate the projected ray angle into world space
+                let ray_angle: f64 =
+                    (player_angle - FOV / 2.0) + (x as f64 / screen.width as f64) * FOV;
+
+                // Unit vector for ray in player space
+                let eye_x = ray_angle.sin();
+                let eye_y = ray_angle.cos();
+
+                // DDA grid traversal: step one map line at a time instead of
+                // marching in fixed-size hops, so the ray can't tunnel through
+                // thin walls and doesn't waste steps crossing open rooms.
+                let delta_dist_x = if eye_x.abs() < 1e-12 {
+                    f64::INFINITY
+                } else {
+                    (1.0 / eye_x).abs()
+                };
+                let delta_dist_y = if eye_y.abs() < 1e-12 {
+                    f64::INFINITY
+                } else {
+                    (1.0 / eye_y).abs()
+                };
+
+                let mut map_x = player_x as i64;
+                let mut map_y = player_y as i64;
+
+                let (step_x, mut side_dist_x) = if eye_x < 0.0 {
+                    (-1i64, (player_x - map_x as f64) * delta_dist_x)
+                } else {
+                    (1i64, (map_x as f64 + 1.0 - player_x) * delta_dist_x)
+                };
+                let (step_y, mut side_dist_y) = if eye_y < 0.0 {
+                    (-1i64, (player_y - map_y as f64) * delta_dist_y)
                 } else {
-                    // Check if ray hit a wall cell
-                    if map[dx as usize][dy as usize] == '#' {
+                    (1i64, (map_y as f64 + 1.0 - player_y) * delta_dist_y)
+                };
+
+                // Which axis was crossed on the hit: x-side or y-side. Needed to
+                // compute the perpendicular (non-fisheye) wall distance below.
+                let mut side_x_hit = true;
+                let mut hit_wall = false;
+                let mut out_of_bounds = false;
+
+                while !hit_wall && !out_of_bounds {
+                    if side_dist_x < side_dist_y {
+                        side_dist_x += delta_dist_x;
+                        map_x += step_x;
+                        side_x_hit = true;
+                    } else {
+                        side_dist_y += delta_dist_y;
+                        map_y += step_y;
+                        side_x_hit = false;
+                    }
+
+                    if !level.contains((map_x as f64, map_y as f64)) {
+                        out_of_bounds = true;
+                    } else if level.tile_at((map_x as f64, map_y as f64)).is_wall() {
                         hit_wall = true;
                     }
                 }
-            }
 
-            // Calculate distance to ceiling and floor
-            let ceiling_index =
-                (screen.height as f64 / 2.0) - (screen.height as f64 / ray_distance);
-            let ceiling_index = ceiling_index as usize;
-            let floor_index = screen.height - ceiling_index;
-
-            for y in 0..screen.height {
-                if y < ceiling_index {
-                    // Ceiling
-                    screen.draw(x, y, " ");
-                } else if y > ceiling_index && y <= floor_index {
-                    // Wall
-                    screen.draw(x, y, &wall_shade(ray_distance).to_string());
+                // Perpendicular distance along the view direction, not the ray
+                // travel length, so straight walls render flat instead of
+                // bowing outward toward the screen edges.
+                let ray_distance = if out_of_bounds {
+                    MAX_RENDER_DIST
+                } else if side_x_hit {
+                    ((map_x as f64 - player_x + (1 - step_x) as f64 / 2.0) / eye_x)
+                        .min(MAX_RENDER_DIST)
                 } else {
-                    // Floor - Shade based on distance
-                    let b = 1.0
-                        - ((y as f64 - screen.height as f64 / 2.0) / (screen.height as f64 / 2.0));
-                    let floor_shade;
-                    if b < 0.25 {
-                        floor_shade = "#";
-                    } else if b < 0.5 {
-                        floor_shade = "x";
-                    } else if b < 0.75 {
-                        floor_shade = ".";
-                    } else if b < 0.9 {
-                        floor_shade = "-";
+                    ((map_y as f64 - player_y + (1 - step_y) as f64 / 2.0) / eye_y)
+                        .min(MAX_RENDER_DIST)
+                };
+
+                let hit_glyph = if out_of_bounds {
+                    '#'
+                } else {
+                    level.tile_at((map_x as f64, map_y as f64)).glyph()
+                };
+
+                // Fractional position of the hit along the struck wall face,
+                // used as the texture's horizontal sample coordinate.
+                let wall_x = if side_x_hit {
+                    player_y + ray_distance * eye_y
+                } else {
+                    player_x + ray_distance * eye_x
+                };
+                let wall_x = wall_x - wall_x.floor();
+
+                // y-side hits get a touch more effective distance so corners
+                // between an x-side and a y-side face read as distinct, the way
+                // a two-tone wall shader would.
+                let shade_distance = if side_x_hit {
+                    ray_distance
+                } else {
+                    ray_distance + MAX_RENDER_DIST / 8.0
+                };
+
+                // Calculate distance to ceiling and floor. `horizon` is the
+                // screen row the flat, infinitely-distant eye-level line would
+                // fall on: normally the middle row, shifted by a pixel amount
+                // proportional to `pitch` for looking up/down. The wall's top
+                // and bottom are then placed relative to `horizon` by how far
+                // the ceiling/floor sit above/below the player's actual eye
+                // height `z`, so crouching or jumping shifts the wall instead
+                // of just the pitch look.
+                let horizon = (screen.height as f64 / 2.0) + player.pitch * screen.height as f64;
+                let half_wall = 2.0 * screen.height as f64 / ray_distance;
+                let ceiling_index = (horizon - (1.0 - player.z) * half_wall)
+                    .max(0.0)
+                    .min(screen.height as f64) as usize;
+                let floor_index = (horizon + player.z * half_wall)
+                    .max(0.0)
+                    .min(screen.height as f64) as usize;
+
+                for y in 0..screen.height {
+                    if y < ceiling_index {
+                        // Ceiling
+                        screen.draw(x, y, " ");
+                    } else if y > ceiling_index && y <= floor_index {
+                        // Wall - sample the struck glyph's texture and shade it
+                        // by distance the same way the flat ramp used to.
+                        let wall_v = if floor_index > ceiling_index {
+                            (y - ceiling_index) as f64 / (floor_index - ceiling_index) as f64
+                        } else {
+                            0.0
+                        };
+                        let sampled = sample_texture(hit_glyph, wall_x, wall_v);
+                        screen.draw(
+                            x,
+                            y,
+                            &shade_texture_glyph(sampled, shade_distance).to_string(),
+                        );
                     } else {
-                        floor_shade = " ";
-                    };
-                    screen.draw(x, y, floor_shade);
+                        // Floor - Shade based on distance
+                        let b = 1.0
+                            - ((y as f64 - screen.height as f64 / 2.0)
+                                / (screen.height as f64 / 2.0));
+                        let floor_shade;
+                        if b < 0.25 {
+                            floor_shade = "#";
+                        } else if b < 0.5 {
+                            floor_shade = "x";
+                        } else if b < 0.75 {
+                            floor_shade = ".";
+                        } else if b < 0.9 {
+                            floor_shade = "-";
+                        } else {
+                            floor_shade = " ";
+                        };
+                        screen.draw(x, y, floor_shade);
+                    }
+                }
+            }
+
+            // Draw the remote player as a billboard marker: a single glyph at
+            // the screen column/row its direction from the local player
+            // projects to, shaded by distance like a wall column would be.
+            if net_mode {
+                let remote = net_state.players[1];
+                let (rx, ry) = (remote.x.to_f64(), remote.y.to_f64());
+                let (dx, dy) = (rx - player_x, ry - player_y);
+                let dist = (dx * dx + dy * dy).sqrt();
+                if dist > 0.1 {
+                    let angle_to_remote = dx.atan2(dy);
+                    let mut rel_angle = angle_to_remote - player_angle;
+                    while rel_angle > std::f64::consts::PI {
+                        rel_angle -= 2.0 * std::f64::consts::PI;
+                    }
+                    while rel_angle < -std::f64::consts::PI {
+                        rel_angle += 2.0 * std::f64::consts::PI;
+                    }
+                    if rel_angle.abs() < FOV / 2.0 {
+                        let col = ((rel_angle + FOV / 2.0) / FOV * screen.width as f64) as usize;
+                        let row = screen.height / 2;
+                        if col < screen.width {
+                            screen.draw(col, row, &wall_shade(dist).to_string());
+                        }
+                    }
                 }
             }
         }
@@ -257,15 +580,26 @@ fn main() {
                 ">"
             };
 
-            for (j, row) in map.iter().enumerate() {
-                for (i, c) in row.iter().enumerate() {
+            for j in 0..level.height {
+                for i in 0..level.width {
                     if player_x == i && player_y == j {
                         screen.draw(i + 2, j + 2, player_icon);
                     } else {
-                        screen.draw(i + 2, j + 2, &c.to_string());
+                        screen.draw(i + 2, j + 2, &level.glyph_at(j, i).to_string());
                     }
                 }
             }
+
+            if net_mode {
+                let remote = net_state.players[1];
+                let (rx, ry) = (remote.x.to_f64() as usize, remote.y.to_f64() as usize);
+                let icon = if net_last_remote_input.fire() {
+                    "!"
+                } else {
+                    "2"
+                };
+                screen.draw(rx + 2, ry + 2, icon);
+            }
         }
 
         // Write stats
@@ -0,0 +1,397 @@
+// Deterministic lockstep netcode for the 2-player shared world.
+//
+// The simulation is advanced by `advance` at a fixed 60 Hz tick, fully
+// decoupled from the variable-rate render loop in `main`. Each peer only
+// ever sends its own local input for a given frame number; the remote
+// player's input for frames not yet received is *predicted* by repeating
+// its last known input. When the real input for a past frame arrives and
+// disagrees with the prediction, we roll back to a buffered snapshot from
+// that frame and re-simulate forward with the corrected input.
+
+use std::net::UdpSocket;
+
+/// Ticks per second the simulation advances at, independent of render FPS.
+pub const TICK_HZ: u32 = 60;
+pub const TICK_DT: Fixed = Fixed::from_ratio(1, TICK_HZ as i64);
+
+/// How many past frames of `GameState` we keep around for rollback.
+pub const ROLLBACK_WINDOW: usize = 8;
+
+/// Q16.16 fixed-point number. Using fixed point (rather than `f64`) for all
+/// simulation math guarantees both peers compute bit-identical results
+/// regardless of platform FP rounding differences.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Fixed(i64);
+
+const FIXED_SHIFT: u32 = 16;
+
+impl Fixed {
+    pub const fn from_int(v: i64) -> Fixed {
+        Fixed(v << FIXED_SHIFT)
+    }
+
+    pub const fn from_ratio(num: i64, den: i64) -> Fixed {
+        Fixed((num << FIXED_SHIFT) / den)
+    }
+
+    pub fn to_f64(self) -> f64 {
+        self.0 as f64 / (1i64 << FIXED_SHIFT) as f64
+    }
+
+    /// Sine via Bhaskara I's approximation, computed entirely in `Fixed`
+    /// integer arithmetic so both peers get bit-identical results - unlike
+    /// `f64::sin`, which is only IEEE-754-deterministic for +,-,*,/ and not
+    /// guaranteed bit-identical for transcendental functions across libm
+    /// implementations.
+    pub fn sin(self) -> Fixed {
+        let mut x = Fixed(self.0 % TWO_PI.0);
+        if x.0 > PI.0 {
+            x = x - TWO_PI;
+        } else if x.0 < -PI.0 {
+            x = x + TWO_PI;
+        }
+        if x.0 >= 0 {
+            sin_bhaskara(x)
+        } else {
+            -sin_bhaskara(-x)
+        }
+    }
+
+    pub fn cos(self) -> Fixed {
+        (self + HALF_PI).sin()
+    }
+}
+
+impl std::ops::Add for Fixed {
+    type Output = Fixed;
+    fn add(self, rhs: Fixed) -> Fixed {
+        Fixed(self.0 + rhs.0)
+    }
+}
+
+impl std::ops::Sub for Fixed {
+    type Output = Fixed;
+    fn sub(self, rhs: Fixed) -> Fixed {
+        Fixed(self.0 - rhs.0)
+    }
+}
+
+impl std::ops::Mul for Fixed {
+    type Output = Fixed;
+    fn mul(self, rhs: Fixed) -> Fixed {
+        Fixed(((self.0 as i128 * rhs.0 as i128) >> FIXED_SHIFT) as i64)
+    }
+}
+
+impl std::ops::Div for Fixed {
+    type Output = Fixed;
+    fn div(self, rhs: Fixed) -> Fixed {
+        Fixed((((self.0 as i128) << FIXED_SHIFT) / rhs.0 as i128) as i64)
+    }
+}
+
+impl std::ops::Neg for Fixed {
+    type Output = Fixed;
+    fn neg(self) -> Fixed {
+        Fixed(-self.0)
+    }
+}
+
+/// Pi, as a rational approximation (355/113, good to 6 decimal places) so it
+/// can be built from the same integer fixed-point constructor as everything
+/// else here, with no floating-point arithmetic involved.
+const PI: Fixed = Fixed::from_ratio(355, 113);
+const TWO_PI: Fixed = Fixed(PI.0 * 2);
+const HALF_PI: Fixed = Fixed(PI.0 / 2);
+
+/// Bhaskara I's rational approximation of sine, valid for `x` in `[0, PI]`,
+/// built entirely out of `Fixed` multiply/subtract/divide so it's exact
+/// integer arithmetic end to end - no calls into the platform's libm, which
+/// is the piece that isn't guaranteed to agree bit-for-bit across peers.
+fn sin_bhaskara(x: Fixed) -> Fixed {
+    let term = x * (PI - x);
+    (Fixed::from_int(16) * term) / (Fixed::from_int(5) * PI * PI - Fixed::from_int(4) * term)
+}
+
+/// A small bitfield of the buttons held during one tick. Packed so it can
+/// be sent as a single byte over the wire.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct PlayerInput(u8);
+
+impl PlayerInput {
+    const FORWARD: u8 = 1 << 0;
+    const BACK: u8 = 1 << 1;
+    const TURN_LEFT: u8 = 1 << 2;
+    const TURN_RIGHT: u8 = 1 << 3;
+    const FIRE: u8 = 1 << 4;
+
+    pub fn new() -> PlayerInput {
+        PlayerInput(0)
+    }
+
+    pub fn set_forward(&mut self, v: bool) {
+        self.set_bit(Self::FORWARD, v);
+    }
+    pub fn set_back(&mut self, v: bool) {
+        self.set_bit(Self::BACK, v);
+    }
+    pub fn set_turn_left(&mut self, v: bool) {
+        self.set_bit(Self::TURN_LEFT, v);
+    }
+    pub fn set_turn_right(&mut self, v: bool) {
+        self.set_bit(Self::TURN_RIGHT, v);
+    }
+    pub fn set_fire(&mut self, v: bool) {
+        self.set_bit(Self::FIRE, v);
+    }
+
+    pub fn forward(self) -> bool {
+        self.0 & Self::FORWARD != 0
+    }
+    pub fn back(self) -> bool {
+        self.0 & Self::BACK != 0
+    }
+    pub fn turn_left(self) -> bool {
+        self.0 & Self::TURN_LEFT != 0
+    }
+    pub fn turn_right(self) -> bool {
+        self.0 & Self::TURN_RIGHT != 0
+    }
+    pub fn fire(self) -> bool {
+        self.0 & Self::FIRE != 0
+    }
+
+    fn set_bit(&mut self, bit: u8, v: bool) {
+        if v {
+            self.0 |= bit;
+        } else {
+            self.0 &= !bit;
+        }
+    }
+}
+
+/// One player's simulated position in the shared world.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct PlayerState {
+    pub x: Fixed,
+    pub y: Fixed,
+    pub angle: Fixed,
+}
+
+/// The full deterministic simulation state for both players, snapshotted
+/// every tick for rollback.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct GameState {
+    pub frame: u32,
+    pub players: [PlayerState; 2],
+}
+
+const PLAYER_V: Fixed = Fixed::from_int(10);
+const PLAYER_ROT_V: Fixed = Fixed::from_ratio(5, 1);
+
+/// Advances `state` by exactly one fixed tick given both players' inputs
+/// for that tick. This is the only place simulation math happens, so both
+/// peers running it over the same `(state, inputs)` pair get the same
+/// result.
+pub fn advance(state: &mut GameState, inputs: [PlayerInput; 2]) {
+    for (player, input) in state.players.iter_mut().zip(inputs.iter()) {
+        if input.turn_left() {
+            player.angle = player.angle - PLAYER_ROT_V * TICK_DT;
+        }
+        if input.turn_right() {
+            player.angle = player.angle + PLAYER_ROT_V * TICK_DT;
+        }
+        if input.forward() {
+            player.x = player.x + player.angle.sin() * PLAYER_V * TICK_DT;
+            player.y = player.y + player.angle.cos() * PLAYER_V * TICK_DT;
+        }
+        if input.back() {
+            player.x = player.x - player.angle.sin() * PLAYER_V * TICK_DT;
+            player.y = player.y - player.angle.cos() * PLAYER_V * TICK_DT;
+        }
+    }
+    state.frame += 1;
+}
+
+/// A single peer's input for a single frame, as exchanged over UDP. On the
+/// wire it is a fixed 5-byte packet (little-endian frame number, then the
+/// input bitfield byte) written and read by `to_bytes`/`from_bytes`, with no
+/// serialization framework needed.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct FrameInput {
+    pub frame: u32,
+    pub input: PlayerInput,
+}
+
+impl FrameInput {
+    pub fn to_bytes(self) -> [u8; 5] {
+        let mut buf = [0u8; 5];
+        buf[0..4].copy_from_slice(&self.frame.to_le_bytes());
+        buf[4] = self.input.0;
+        buf
+    }
+
+    pub fn from_bytes(buf: &[u8]) -> Option<FrameInput> {
+        if buf.len() < 5 {
+            return None;
+        }
+        let frame = u32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]]);
+        Some(FrameInput {
+            frame,
+            input: PlayerInput(buf[4]),
+        })
+    }
+}
+
+/// Ring buffer of past states plus the inputs used to predict frames that
+/// haven't been confirmed by the remote peer yet.
+pub struct Rollback {
+    snapshots: [GameState; ROLLBACK_WINDOW],
+    predicted_remote_input: [PlayerInput; ROLLBACK_WINDOW],
+}
+
+impl Rollback {
+    pub fn new(initial: GameState) -> Rollback {
+        Rollback {
+            snapshots: [initial; ROLLBACK_WINDOW],
+            predicted_remote_input: [PlayerInput::new(); ROLLBACK_WINDOW],
+        }
+    }
+
+    fn slot(frame: u32) -> usize {
+        frame as usize % ROLLBACK_WINDOW
+    }
+
+    /// Records the state at the start of `frame`, before `advance` runs,
+    /// along with the remote input we predicted for it.
+    pub fn record(&mut self, frame: u32, state: GameState, predicted_remote: PlayerInput) {
+        let slot = Self::slot(frame);
+        self.snapshots[slot] = state;
+        self.predicted_remote_input[slot] = predicted_remote;
+    }
+
+    /// Given the now-confirmed remote input for `frame`, re-simulates from
+    /// that frame up to `current`, correcting the misprediction.
+    pub fn reconcile(
+        &mut self,
+        frame: u32,
+        confirmed_remote: PlayerInput,
+        current: GameState,
+        local_inputs: impl Fn(u32) -> PlayerInput,
+        remote_inputs: impl Fn(u32) -> PlayerInput,
+    ) -> GameState {
+        if current.frame.saturating_sub(frame) as usize > ROLLBACK_WINDOW {
+            // The snapshot for `frame` has already been overwritten by a
+            // newer frame sharing its ring buffer slot (a packet this late
+            // shouldn't happen on a healthy connection, but arrives
+            // correctly-addressed UDP can still be this stale). Resimulating
+            // from that aliased snapshot would produce a worse result than
+            // just keeping the misprediction, so drop the correction.
+            return current;
+        }
+
+        let slot = Self::slot(frame);
+        if self.predicted_remote_input[slot] == confirmed_remote {
+            // Prediction was correct; nothing to redo.
+            return current;
+        }
+
+        let mut state = self.snapshots[slot];
+        let mut f = frame;
+        while f < current.frame {
+            let remote = if f == frame {
+                confirmed_remote
+            } else {
+                remote_inputs(f)
+            };
+            advance(&mut state, [local_inputs(f), remote]);
+            f += 1;
+        }
+        state
+    }
+}
+
+/// Command-line network configuration: the local socket to bind and the
+/// set of remote peers to exchange frame input with.
+pub struct NetConfig {
+    pub local_port: u16,
+    pub peers: Vec<std::net::SocketAddr>,
+}
+
+impl NetConfig {
+    /// Parses `--port <n>` and one or more `--peer <addr>` flags out of the
+    /// process arguments.
+    pub fn from_args(args: &[String]) -> Option<NetConfig> {
+        let mut local_port = None;
+        let mut peers = Vec::new();
+        let mut i = 0;
+        while i < args.len() {
+            match args[i].as_str() {
+                "--port" => {
+                    local_port = args.get(i + 1).and_then(|s| s.parse().ok());
+                    i += 2;
+                }
+                "--peer" => {
+                    if let Some(addr) = args.get(i + 1).and_then(|s| s.parse().ok()) {
+                        peers.push(addr);
+                    }
+                    i += 2;
+                }
+                _ => i += 1,
+            }
+        }
+        local_port.map(|local_port| NetConfig { local_port, peers })
+    }
+}
+
+/// Opens the local UDP socket for a `NetConfig`, non-blocking so the render
+/// loop never stalls waiting on the network.
+pub fn bind(config: &NetConfig) -> std::io::Result<UdpSocket> {
+    let socket = UdpSocket::bind(("0.0.0.0", config.local_port))?;
+    socket.set_nonblocking(true)?;
+    Ok(socket)
+}
+
+/// Sends this tick's local input to every configured peer.
+pub fn send_input(socket: &UdpSocket, config: &NetConfig, input: FrameInput) {
+    let bytes = input.to_bytes();
+    for peer in &config.peers {
+        let _ = socket.send_to(&bytes, peer);
+    }
+}
+
+/// Drains any pending inbound packets, returning the decoded frame inputs
+/// in the order received.
+pub fn poll_inputs(socket: &UdpSocket) -> Vec<FrameInput> {
+    let mut out = Vec::new();
+    let mut buf = [0u8; 5];
+    while let Ok((n, _)) = socket.recv_from(&mut buf) {
+        if let Some(frame_input) = FrameInput::from_bytes(&buf[..n]) {
+            out.push(frame_input);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn advance_is_bit_reproducible() {
+        let mut moving = PlayerInput::new();
+        moving.set_forward(true);
+        moving.set_turn_right(true);
+        let inputs = [moving, PlayerInput::new()];
+
+        let mut a = GameState::default();
+        let mut b = GameState::default();
+        for _ in 0..240 {
+            advance(&mut a, inputs);
+            advance(&mut b, inputs);
+        }
+
+        assert_eq!(a, b);
+    }
+}
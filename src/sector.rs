@@ -0,0 +1,331 @@
+// Sector/portal world model.
+//
+// This is an alternate geometry representation to the flat tile grid
+// (`engine::Level`) used by the raycaster in `main`. Instead of a uniform-height wall per
+// cell, the world is a graph of convex `Sector`s connected by portal edges,
+// which lets rooms have independent floor/ceiling heights (steps, balconies,
+// etc). Rendering walks the graph with a render queue seeded by the
+// player's current sector, drawing the nearest solid geometry first and
+// recursing through portals with a narrowed screen-column range.
+
+use crate::{Screen, FOV};
+
+/// A single point in 2D world space, shared by a sector's vertex loop.
+pub type Vec2 = (f64, f64);
+
+/// A convex room. `vertices` form a closed polygon (edge `i` runs from
+/// `vertices[i]` to `vertices[(i + 1) % vertices.len()]`); `neighbors[i]` is
+/// the sector on the far side of that same edge, or `None` if the edge is a
+/// solid wall.
+pub struct Sector {
+    pub floor: f64,
+    pub ceil: f64,
+    pub vertices: Vec<Vec2>,
+    pub neighbors: Vec<Option<usize>>,
+}
+
+impl Sector {
+    pub fn new(floor: f64, ceil: f64, vertices: Vec<Vec2>) -> Sector {
+        let neighbors = vec![None; vertices.len()];
+        Sector {
+            floor,
+            ceil,
+            vertices,
+            neighbors,
+        }
+    }
+
+    /// Connects edge `edge_index` of this sector to `other`. Does not touch
+    /// the reverse edge on `other` - callers wire both directions.
+    pub fn set_portal(&mut self, edge_index: usize, other: usize) {
+        self.neighbors[edge_index] = Some(other);
+    }
+
+    fn edge(&self, i: usize) -> (Vec2, Vec2) {
+        let a = self.vertices[i];
+        let b = self.vertices[(i + 1) % self.vertices.len()];
+        (a, b)
+    }
+}
+
+/// The player's position within the sector world, including which sector
+/// they currently occupy (needed since sectors aren't a uniform grid).
+pub struct Player {
+    pub pos: Vec2,
+    pub angle: f64,
+    pub z: f64,
+    /// Look up/down offset, in radians - mirrors `engine::Player::pitch`.
+    pub pitch: f64,
+    pub sector: usize,
+}
+
+pub struct World {
+    pub sectors: Vec<Sector>,
+}
+
+impl World {
+    pub fn new(sectors: Vec<Sector>) -> World {
+        World { sectors }
+    }
+
+    /// Finds which sector (if any) contains `pos`, starting the search from
+    /// `hint` (usually the player's last-known sector) since sectors are
+    /// small and adjacent frames rarely change rooms.
+    pub fn locate(&self, pos: Vec2, hint: usize) -> Option<usize> {
+        if self.point_in_sector(hint, pos) {
+            return Some(hint);
+        }
+        (0..self.sectors.len()).find(|&i| self.point_in_sector(i, pos))
+    }
+
+    fn point_in_sector(&self, index: usize, pos: Vec2) -> bool {
+        let sector = &self.sectors[index];
+        let mut inside = false;
+        let n = sector.vertices.len();
+        let mut j = n - 1;
+        for i in 0..n {
+            let (xi, yi) = sector.vertices[i];
+            let (xj, yj) = sector.vertices[j];
+            if ((yi > pos.1) != (yj > pos.1)) && (pos.0 < (xj - xi) * (pos.1 - yi) / (yj - yi) + xi)
+            {
+                inside = !inside;
+            }
+            j = i;
+        }
+        inside
+    }
+}
+
+/// View-space transform: translate by the player's position, then undo the
+/// player's `angle` (measured from +y toward +x, matching the `(sin, cos)`
+/// facing used for movement) so the player faces down the +y axis in view
+/// space.
+fn to_view_space(p: Vec2, player: &Player) -> Vec2 {
+    let (dx, dy) = (p.0 - player.pos.0, p.1 - player.pos.1);
+    let (sin_a, cos_a) = player.angle.sin_cos();
+    (dx * cos_a - dy * sin_a, dx * sin_a + dy * cos_a)
+}
+
+/// Clips a view-space edge against the near plane (`y >= NEAR`), returning
+/// `None` if the whole edge is behind the camera.
+fn clip_near(mut a: Vec2, mut b: Vec2) -> Option<(Vec2, Vec2)> {
+    const NEAR: f64 = 0.05;
+    if a.1 < NEAR && b.1 < NEAR {
+        return None;
+    }
+    if a.1 < NEAR {
+        let t = (NEAR - a.1) / (b.1 - a.1);
+        a = (a.0 + (b.0 - a.0) * t, NEAR);
+    } else if b.1 < NEAR {
+        let t = (NEAR - b.1) / (a.1 - b.1);
+        b = (b.0 + (a.0 - b.0) * t, NEAR);
+    }
+    Some((a, b))
+}
+
+/// Projects a view-space x/y pair to a screen column, given the horizontal
+/// half-FOV scale factor.
+fn project_column(p: Vec2, screen_width: usize) -> f64 {
+    let hfov_scale = (FOV / 2.0).tan();
+    let ndc_x = (p.0 / p.1) / hfov_scale;
+    (0.5 - ndc_x * 0.5) * screen_width as f64
+}
+
+/// Projects a world-space height (relative to the player's eye) at view
+/// distance `dist` to a screen row. `pitch` shifts the whole row by a pixel
+/// amount proportional to the look angle, the same way the flat raycaster's
+/// horizon shifts in `main`.
+fn project_row(
+    height_above_eye: f64,
+    dist: f64,
+    screen_height: usize,
+    vfov: f64,
+    pitch: f64,
+) -> f64 {
+    let vfov_scale = (vfov / 2.0).tan();
+    let ndc_y = (height_above_eye / dist) / vfov_scale;
+    (0.5 - ndc_y * 0.5) * screen_height as f64 + pitch * screen_height as f64
+}
+
+struct QueueEntry {
+    sector: usize,
+    x_lo: usize,
+    x_hi: usize,
+}
+
+/// Renders the sector world into `screen` from the player's point of view.
+/// `vfov` is the vertical field of view in radians.
+pub fn render(screen: &mut Screen, world: &World, player: &Player, vfov: f64) {
+    let mut ytop = vec![0usize; screen.width];
+    let mut ybottom = vec![screen.height; screen.width];
+
+    let mut queue = vec![QueueEntry {
+        sector: player.sector,
+        x_lo: 0,
+        x_hi: screen.width,
+    }];
+    let mut visited = std::collections::HashSet::new();
+
+    while let Some(entry) = queue.pop() {
+        // Guard against portal cycles feeding the same sector/range back in.
+        if !visited.insert((entry.sector, entry.x_lo, entry.x_hi)) {
+            continue;
+        }
+        let sector = &world.sectors[entry.sector];
+        let edge_count = sector.vertices.len();
+
+        for i in 0..edge_count {
+            let (a, b) = sector.edge(i);
+            let (va, vb) = (to_view_space(a, player), to_view_space(b, player));
+            let clipped = match clip_near(va, vb) {
+                Some(c) => c,
+                None => continue,
+            };
+
+            let col_a = project_column(clipped.0, screen.width);
+            let col_b = project_column(clipped.1, screen.width);
+            let (col_lo, col_hi) = if col_a <= col_b {
+                (col_a, col_b)
+            } else {
+                (col_b, col_a)
+            };
+            let x_lo = (col_lo.max(entry.x_lo as f64)) as usize;
+            let x_hi = (col_hi.min(entry.x_hi as f64)).ceil() as usize;
+            if x_lo >= x_hi || x_lo >= screen.width {
+                continue;
+            }
+            let x_hi = x_hi.min(screen.width);
+
+            for x in x_lo..x_hi {
+                // Distance along the view direction at this column, linearly
+                // interpolated across the clipped edge.
+                let t = if (col_b - col_a).abs() > 1e-9 {
+                    ((x as f64 - col_a) / (col_b - col_a)).clamp(0.0, 1.0)
+                } else {
+                    0.0
+                };
+                let dist = clipped.0 .1 + (clipped.1 .1 - clipped.0 .1) * t;
+                if dist <= 0.0 {
+                    continue;
+                }
+
+                let ceil_y = project_row(
+                    sector.ceil - player.z,
+                    dist,
+                    screen.height,
+                    vfov,
+                    player.pitch,
+                )
+                .max(ytop[x] as f64);
+                let floor_y = project_row(
+                    sector.floor - player.z,
+                    dist,
+                    screen.height,
+                    vfov,
+                    player.pitch,
+                )
+                .min(ybottom[x] as f64);
+
+                match sector.neighbors[i] {
+                    None => {
+                        // Solid wall: fill from current ceiling clamp to
+                        // floor clamp and mask this column for farther draws.
+                        draw_span(
+                            screen,
+                            x,
+                            ceil_y as usize,
+                            floor_y as usize,
+                            wall_glyph(dist),
+                        );
+                        ytop[x] = ybottom[x];
+                    }
+                    Some(neighbor_index) => {
+                        let neighbor = &world.sectors[neighbor_index];
+                        // Only the floor/ceiling "step" differences are
+                        // solid; the rest of the portal stays open so the
+                        // neighbor sector renders through it.
+                        let n_ceil_y = project_row(
+                            neighbor.ceil - player.z,
+                            dist,
+                            screen.height,
+                            vfov,
+                            player.pitch,
+                        );
+                        let n_floor_y = project_row(
+                            neighbor.floor - player.z,
+                            dist,
+                            screen.height,
+                            vfov,
+                            player.pitch,
+                        );
+
+                        if neighbor.ceil < sector.ceil {
+                            draw_span(
+                                screen,
+                                x,
+                                ceil_y as usize,
+                                n_ceil_y as usize,
+                                wall_glyph(dist),
+                            );
+                        }
+                        if neighbor.floor > sector.floor {
+                            draw_span(
+                                screen,
+                                x,
+                                n_floor_y as usize,
+                                floor_y as usize,
+                                wall_glyph(dist),
+                            );
+                        }
+
+                        ytop[x] = ytop[x].max(n_ceil_y.max(ceil_y) as usize);
+                        ybottom[x] = ybottom[x].min(n_floor_y.min(floor_y) as usize);
+
+                        queue.push(QueueEntry {
+                            sector: neighbor_index,
+                            x_lo,
+                            x_hi,
+                        });
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn draw_span(screen: &mut Screen, x: usize, y_from: usize, y_to: usize, glyph: char) {
+    let (lo, hi) = if y_from <= y_to {
+        (y_from, y_to)
+    } else {
+        (y_to, y_from)
+    };
+    for y in lo..hi.min(screen.height) {
+        screen.draw(x, y, &glyph.to_string());
+    }
+}
+
+fn wall_glyph(dist: f64) -> char {
+    crate::wall_shade(dist)
+}
+
+/// A small two-room demo world: a tall entry room connected through a
+/// stepped-down portal to a lower room, so `--sectors` has something to
+/// show without needing a map file format yet.
+pub fn demo_world() -> World {
+    let mut entry = Sector::new(
+        0.0,
+        3.0,
+        vec![(0.0, 0.0), (5.0, 0.0), (5.0, 5.0), (0.0, 5.0)],
+    );
+    let mut hall = Sector::new(
+        -0.5,
+        2.0,
+        vec![(5.0, 1.0), (9.0, 1.0), (9.0, 4.0), (5.0, 4.0)],
+    );
+
+    // Edge 1 of `entry` (5,0)-(5,5) opens onto edge 3 of `hall` (5,4)-(5,1).
+    entry.set_portal(1, 1);
+    hall.set_portal(3, 0);
+
+    World::new(vec![entry, hall])
+}